@@ -40,6 +40,11 @@ impl Binary {
         return self.data.len();
     }
 
+    /// 获取二进制数据的原始字节切片
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
 }
 
 impl ToString for Binary {
@@ -0,0 +1,215 @@
+//! Compact binary wire format for `DataValue`.
+//!
+//! Each value is encoded as a single tag byte followed by a payload. Lengths
+//! and counts use unsigned LEB128 so small collections stay small. This is
+//! meant to be denser than the `to_json`/base64 round-trip, especially for
+//! `Binary` payloads.
+
+use std::collections::HashMap;
+
+use anyhow::{Result, anyhow, bail};
+
+use crate::DataValue;
+use crate::binary::Binary;
+
+const TAG_NONE: u8 = 0x00;
+const TAG_BOOLEAN: u8 = 0x01;
+const TAG_NUMBER: u8 = 0x02;
+const TAG_STRING: u8 = 0x03;
+const TAG_LIST: u8 = 0x04;
+const TAG_DICT: u8 = 0x05;
+const TAG_TUPLE: u8 = 0x06;
+const TAG_BINARY: u8 = 0x07;
+const TAG_INT: u8 = 0x08;
+const TAG_UINT: u8 = 0x09;
+
+fn write_uleb128(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_uleb128(data: &[u8]) -> Result<(u64, usize)> {
+    let mut result: u64 = 0;
+    let mut shift = 0;
+    let mut consumed = 0;
+
+    for &byte in data {
+        consumed += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok((result, consumed));
+        }
+
+        shift += 7;
+        if shift >= 64 {
+            bail!("leb128 value too large");
+        }
+    }
+
+    bail!("truncated leb128 value")
+}
+
+fn take(data: &[u8], len: usize) -> Result<&[u8]> {
+    if data.len() < len {
+        bail!("truncated input: expected {} bytes, got {}", len, data.len());
+    }
+    Ok(&data[..len])
+}
+
+pub fn encode(value: &DataValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_into(value, &mut out);
+    out
+}
+
+fn encode_into(value: &DataValue, out: &mut Vec<u8>) {
+    match value {
+        DataValue::None => out.push(TAG_NONE),
+        DataValue::Boolean(b) => {
+            out.push(TAG_BOOLEAN);
+            out.push(if *b { 1 } else { 0 });
+        }
+        DataValue::Number(n) => {
+            out.push(TAG_NUMBER);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        DataValue::Int(n) => {
+            out.push(TAG_INT);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        DataValue::UInt(n) => {
+            out.push(TAG_UINT);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        DataValue::String(s) => {
+            out.push(TAG_STRING);
+            write_uleb128(out, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+        DataValue::List(list) => {
+            out.push(TAG_LIST);
+            write_uleb128(out, list.len() as u64);
+            for item in list {
+                encode_into(item, out);
+            }
+        }
+        DataValue::Dict(dict) => {
+            out.push(TAG_DICT);
+            write_uleb128(out, dict.len() as u64);
+            for (key, item) in dict {
+                write_uleb128(out, key.len() as u64);
+                out.extend_from_slice(key.as_bytes());
+                encode_into(item, out);
+            }
+        }
+        DataValue::Tuple(tuple) => {
+            out.push(TAG_TUPLE);
+            encode_into(&tuple.0, out);
+            encode_into(&tuple.1, out);
+        }
+        DataValue::Binary(bin) => {
+            out.push(TAG_BINARY);
+            write_uleb128(out, bin.data().len() as u64);
+            out.extend_from_slice(bin.data());
+        }
+    }
+}
+
+/// Decodes one value from the front of `data`, returning it alongside the
+/// number of bytes consumed so callers can recurse over concatenated values.
+pub fn decode(data: &[u8]) -> Result<(DataValue, usize)> {
+    if data.is_empty() {
+        bail!("truncated input: expected a tag byte");
+    }
+
+    let tag = data[0];
+    let mut offset = 1;
+
+    let value = match tag {
+        TAG_NONE => DataValue::None,
+        TAG_BOOLEAN => {
+            let byte = *take(&data[offset..], 1)?.first().unwrap();
+            offset += 1;
+            DataValue::Boolean(byte != 0)
+        }
+        TAG_NUMBER => {
+            let bytes = take(&data[offset..], 8)?;
+            offset += 8;
+            DataValue::Number(f64::from_le_bytes(bytes.try_into().unwrap()))
+        }
+        TAG_INT => {
+            let bytes = take(&data[offset..], 8)?;
+            offset += 8;
+            DataValue::Int(i64::from_le_bytes(bytes.try_into().unwrap()))
+        }
+        TAG_UINT => {
+            let bytes = take(&data[offset..], 8)?;
+            offset += 8;
+            DataValue::UInt(u64::from_le_bytes(bytes.try_into().unwrap()))
+        }
+        TAG_STRING => {
+            let (len, consumed) = read_uleb128(&data[offset..])?;
+            offset += consumed;
+            let bytes = take(&data[offset..], len as usize)?;
+            offset += len as usize;
+            DataValue::String(String::from_utf8(bytes.to_vec())?)
+        }
+        TAG_LIST => {
+            let (count, consumed) = read_uleb128(&data[offset..])?;
+            offset += consumed;
+            // count 来自输入字节，可能是伪造的超大值；按剩余字节数夹住，
+            // 避免在真正读到 Err 之前就因 capacity overflow panic
+            let mut list = Vec::with_capacity(count.min(data.len() as u64) as usize);
+            for _ in 0..count {
+                let (item, item_consumed) = decode(&data[offset..])?;
+                offset += item_consumed;
+                list.push(item);
+            }
+            DataValue::List(list)
+        }
+        TAG_DICT => {
+            let (count, consumed) = read_uleb128(&data[offset..])?;
+            offset += consumed;
+            // 同上：count 夹住到剩余字节数，避免伪造的超大计数触发 capacity overflow
+            let mut dict = HashMap::with_capacity(count.min(data.len() as u64) as usize);
+            for _ in 0..count {
+                let (key_len, key_consumed) = read_uleb128(&data[offset..])?;
+                offset += key_consumed;
+                let key_bytes = take(&data[offset..], key_len as usize)?;
+                offset += key_len as usize;
+                let key = String::from_utf8(key_bytes.to_vec())?;
+
+                let (value, value_consumed) = decode(&data[offset..])?;
+                offset += value_consumed;
+
+                dict.insert(key, value);
+            }
+            DataValue::Dict(dict)
+        }
+        TAG_TUPLE => {
+            let (first, first_consumed) = decode(&data[offset..])?;
+            offset += first_consumed;
+            let (second, second_consumed) = decode(&data[offset..])?;
+            offset += second_consumed;
+            DataValue::Tuple((Box::new(first), Box::new(second)))
+        }
+        TAG_BINARY => {
+            let (len, consumed) = read_uleb128(&data[offset..])?;
+            offset += consumed;
+            let bytes = take(&data[offset..], len as usize)?;
+            offset += len as usize;
+            DataValue::Binary(Binary::build(bytes.to_vec()))
+        }
+        other => return Err(anyhow!("unknown tag byte: {:#04x}", other)),
+    };
+
+    Ok((value, offset))
+}
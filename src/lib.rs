@@ -19,8 +19,17 @@
 //! ```
 
 mod binary;
+mod codec;
+mod options;
+mod schema;
+mod select;
+mod stream;
 
 use binary::Binary;
+use select::Selector;
+
+pub use options::ParserOptions;
+pub use schema::{Schema, ValidationError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -50,6 +59,27 @@ pub enum DataValue {
     /// ```
     Number(f64),
 
+    /// Signed integer Value
+    ///
+    /// Used for integer literals so they don't lose precision by going
+    /// through `f64`, which only represents integers exactly up to 2^53.
+    ///
+    /// ```
+    /// use doson::DataValue;
+    /// DataValue::Int(-10);
+    /// ```
+    Int(i64),
+
+    /// Unsigned integer Value
+    ///
+    /// Used for integer literals that overflow `i64` but still fit `u64`.
+    ///
+    /// ```
+    /// use doson::DataValue;
+    /// DataValue::UInt(10);
+    /// ```
+    UInt(u64),
+
     /// Boolean Value
     ///
     /// ```
@@ -100,6 +130,8 @@ impl std::string::ToString for DataValue {
             DataValue::None => "none".to_string(),
             DataValue::String(s) => format!("\"{}\"", s),
             DataValue::Number(n) => n.to_string(),
+            DataValue::Int(n) => n.to_string(),
+            DataValue::UInt(n) => n.to_string(),
             DataValue::Boolean(bool) => match bool {
                 true => "true".to_string(),
                 false => "false".to_string(),
@@ -143,9 +175,71 @@ impl std::string::ToString for DataValue {
     }
 }
 
+impl DataValue {
+    // 数据类型的排序优先级，仅在两个值类型不同（且不都是数字类型）的情况下使用
+    fn rank(&self) -> u8 {
+        match self {
+            DataValue::None => 0,
+            DataValue::Boolean(_) => 1,
+            DataValue::Int(_) => 2,
+            DataValue::UInt(_) => 3,
+            DataValue::Number(_) => 4,
+            DataValue::String(_) => 5,
+            DataValue::Binary(_) => 6,
+            DataValue::List(_) => 7,
+            DataValue::Tuple(_) => 8,
+            DataValue::Dict(_) => 9,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, DataValue::Int(_) | DataValue::UInt(_) | DataValue::Number(_))
+    }
+}
+
+// Int/UInt/Number 都代表数字，互相比较时按数值大小比较，而不是退化成类型优先级；
+// Int 与 UInt 之间直接按整数比较以避免精度损失，只有涉及 Number 时才转换为 f64。
+fn numeric_cmp(a: &DataValue, b: &DataValue) -> Ordering {
+    match (a, b) {
+        (DataValue::Int(a), DataValue::Int(b)) => a.cmp(b),
+        (DataValue::UInt(a), DataValue::UInt(b)) => a.cmp(b),
+        (DataValue::Int(a), DataValue::UInt(b)) => {
+            if *a < 0 { Ordering::Less } else { (*a as u64).cmp(b) }
+        }
+        (DataValue::UInt(a), DataValue::Int(b)) => {
+            if *b < 0 { Ordering::Greater } else { a.cmp(&(*b as u64)) }
+        }
+        (DataValue::Number(a), DataValue::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (DataValue::Int(a), DataValue::Number(b)) => (*a as f64).partial_cmp(b).unwrap_or(Ordering::Equal),
+        (DataValue::Number(a), DataValue::Int(b)) => a.partial_cmp(&(*b as f64)).unwrap_or(Ordering::Equal),
+        (DataValue::UInt(a), DataValue::Number(b)) => (*a as f64).partial_cmp(b).unwrap_or(Ordering::Equal),
+        (DataValue::Number(a), DataValue::UInt(b)) => a.partial_cmp(&(*b as f64)).unwrap_or(Ordering::Equal),
+        _ => unreachable!("numeric_cmp called with a non-numeric operand"),
+    }
+}
+
 impl std::cmp::Ord for DataValue {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.weight().partial_cmp(&other.weight()).unwrap_or(Ordering::Equal)
+        match (self, other) {
+            (DataValue::None, DataValue::None) => Ordering::Equal,
+            (DataValue::Boolean(a), DataValue::Boolean(b)) => a.cmp(b),
+            (a, b) if a.is_numeric() && b.is_numeric() => numeric_cmp(a, b),
+            (DataValue::String(a), DataValue::String(b)) => a.cmp(b),
+            (DataValue::Binary(a), DataValue::Binary(b)) => a.data().cmp(b.data()),
+            (DataValue::List(a), DataValue::List(b)) => a.cmp(b),
+            (DataValue::Tuple(a), DataValue::Tuple(b)) => {
+                a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1))
+            }
+            (DataValue::Dict(a), DataValue::Dict(b)) => {
+                // HashMap 的遍历顺序不固定，按 key 排序后再逐项比较，保证结果确定
+                let mut a_entries: Vec<_> = a.iter().collect();
+                let mut b_entries: Vec<_> = b.iter().collect();
+                a_entries.sort_by(|x, y| x.0.cmp(y.0));
+                b_entries.sort_by(|x, y| x.0.cmp(y.0));
+                a_entries.cmp(&b_entries)
+            }
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
     }
 }
 
@@ -157,7 +251,20 @@ impl std::cmp::PartialOrd for DataValue {
 
 impl std::cmp::PartialEq for DataValue {
     fn eq(&self, other: &Self) -> bool {
-        self.to_string() == other.to_string()
+        match (self, other) {
+            (DataValue::None, DataValue::None) => true,
+            (DataValue::Boolean(a), DataValue::Boolean(b)) => a == b,
+            (a, b) if a.is_numeric() && b.is_numeric() => numeric_cmp(a, b) == Ordering::Equal,
+            (DataValue::String(a), DataValue::String(b)) => a == b,
+            (DataValue::Binary(a), DataValue::Binary(b)) => a.data() == b.data(),
+            (DataValue::List(a), DataValue::List(b)) => a == b,
+            (DataValue::Tuple(a), DataValue::Tuple(b)) => a.0 == b.0 && a.1 == b.1,
+            (DataValue::Dict(a), DataValue::Dict(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(k, v)| b.get(k).is_some_and(|bv| v == bv))
+            }
+            _ => false,
+        }
     }
 }
 
@@ -178,26 +285,65 @@ impl DataValue {
     /// assert_eq!(
     ///     DataValue::from("[1,2,3]"),
     ///     DataValue::List(vec![
-    ///         DataValue::Number(1_f64),
-    ///         DataValue::Number(2_f64),
-    ///         DataValue::Number(3_f64),
+    ///         DataValue::Int(1),
+    ///         DataValue::Int(2),
+    ///         DataValue::Int(3),
     ///     ])
     /// );
     /// ```
     pub fn from(data: &str) -> Self {
+        Self::from_with(data, &ParserOptions::default()).unwrap_or(Self::None)
+    }
 
-        let mut data = data.to_string();
-        if data.len() >= 3 {
-            if &data[0..2] == "b:" && &data[data.len() - 1..] == ":" {
-                let temp = &data[2 .. data.len() - 1];
-                let temp = base64::decode(temp).unwrap_or(vec![]);
-                data = String::from_utf8(temp).unwrap_or(String::new());
+    /// Like [`DataValue::from`], but with configurable lenient-parsing
+    /// behavior: trailing commas, `//`/`/* */` comments, unquoted Dict keys,
+    /// and an optional strict mode.
+    ///
+    /// `from` is a thin wrapper over this with [`ParserOptions::default`],
+    /// so its behavior is unchanged: malformed input silently yields
+    /// `DataValue::None`. With `options.strict(true)`, malformed input or
+    /// unconsumed trailing input returns a descriptive error with the byte
+    /// offset (into `data`, before any lenient rewriting; for `b:<base64>:`
+    /// input, into the decoded text) where parsing stopped, instead.
+    ///
+    /// ```
+    /// use doson::{DataValue, ParserOptions};
+    ///
+    /// let options = ParserOptions::new().trailing_commas(true).comments(true).unquoted_keys(true);
+    /// assert_eq!(
+    ///     DataValue::from_with("{foo: 1, /* trailing */ bar: 2,}", &options).unwrap(),
+    ///     DataValue::from("{\"foo\": 1, \"bar\": 2}")
+    /// );
+    /// ```
+    pub fn from_with(data: &str, options: &ParserOptions) -> anyhow::Result<DataValue> {
+        let data = unwrap_b64_shorthand(data);
+        let preprocessed = options::preprocess(&data, options);
+
+        match parse_with_rest(&preprocessed.text) {
+            Ok((value, rest)) => {
+                if options.strict && !rest.trim().is_empty() {
+                    let processed_offset = preprocessed.text.len() - rest.len();
+                    let offset = preprocessed.original_offset(processed_offset);
+                    return Err(anyhow::anyhow!(
+                        "unexpected trailing input at byte offset {}: `{}`",
+                        offset,
+                        data[offset..].trim_start().chars().take(20).collect::<String>()
+                    ));
+                }
+                Ok(value)
+            }
+            Err(processed_offset) => {
+                if options.strict {
+                    let offset = preprocessed.original_offset(processed_offset);
+                    Err(anyhow::anyhow!(
+                        "parse error at byte offset {}: unexpected input `{}`",
+                        offset,
+                        data[offset..].chars().take(20).collect::<String>()
+                    ))
+                } else {
+                    Ok(Self::None)
+                }
             }
-        }
-
-        match ValueParser::parse(&data) {
-            Ok((_, v)) => v,
-            Err(_) => Self::None,
         }
     }
 
@@ -205,8 +351,41 @@ impl DataValue {
         serde_json::to_string(&self).unwrap_or(String::from("None"))
     }
 
+    /// Encodes this value into the compact tagged binary wire format.
+    ///
+    /// ```
+    /// use doson::DataValue;
+    ///
+    /// let value = DataValue::Number(1_f64);
+    /// assert_eq!(
+    ///     DataValue::from_bytes(&value.to_bytes()).unwrap(),
+    ///     value
+    /// );
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    /// Decodes a value previously produced by [`DataValue::to_bytes`].
+    ///
+    /// Returns an error if `data` is truncated, carries an unknown tag byte,
+    /// or has trailing bytes left over after a complete value is decoded.
+    pub fn from_bytes(data: &[u8]) -> anyhow::Result<DataValue> {
+        let (value, consumed) = codec::decode(data)?;
+
+        if consumed != data.len() {
+            return Err(anyhow::anyhow!(
+                "trailing bytes after decoding: consumed {} of {}",
+                consumed,
+                data.len()
+            ));
+        }
+
+        Ok(value)
+    }
+
     // 数据权值计算
-    // Number(f64) 的权值等于它本身
+    // Number(f64)/Int(i64)/UInt(u64) 的权值等于它本身
     // 其他基本类型的权值为 f64::MAX
     // 复合类型则会进行递归计算
     // 权值主要用于排序等操作
@@ -216,6 +395,14 @@ impl DataValue {
             return *n;
         }
 
+        if let DataValue::Int(n) = self {
+            return *n as f64;
+        }
+
+        if let DataValue::UInt(n) = self {
+            return *n as f64;
+        }
+
         // 计算数组的权重值
         if let DataValue::List(l) = self {
             let mut total = 0_f64;
@@ -261,6 +448,8 @@ impl DataValue {
             DataValue::None => 0,
             DataValue::String(str) => str.len(),
             DataValue::Number(_) => 8,
+            DataValue::Int(_) => 8,
+            DataValue::UInt(_) => 8,
             DataValue::Boolean(_) => 1,
 
             DataValue::List(list) => {
@@ -292,6 +481,8 @@ impl DataValue {
             DataValue::None => "None",
             DataValue::String(_) => "String",
             DataValue::Number(_) => "Number",
+            DataValue::Int(_) => "Int",
+            DataValue::UInt(_) => "UInt",
             DataValue::Boolean(_) => "Boolean",
             DataValue::List(_) => "List",
             DataValue::Dict(_) => "Dict",
@@ -315,6 +506,20 @@ impl DataValue {
         }
     }
 
+    pub fn as_int(&self) -> Option<i64> {
+        return match self {
+            DataValue::Int(val) => Some(*val),
+            _ => None
+        }
+    }
+
+    pub fn as_uint(&self) -> Option<u64> {
+        return match self {
+            DataValue::UInt(val) => Some(*val),
+            _ => None
+        }
+    }
+
 
     pub fn as_bool(&self) -> Option<bool> {
         return match self {
@@ -344,6 +549,71 @@ impl DataValue {
         }
     }
 
+    /// Selects every node matched by a JSONPath-like selector string.
+    ///
+    /// Supports `.key` (Dict field, or `.0`/`.1` for a Tuple), `[n]` (List or
+    /// Tuple index), `[*]` (all children), `..` (recursive descent), and
+    /// `[?key==value]`/`[?key>value]` predicate filters on Dict nodes.
+    /// A malformed selector yields no matches rather than panicking.
+    ///
+    /// ```
+    /// use doson::DataValue;
+    ///
+    /// let value = DataValue::from("{\"name\": \"doson\"}");
+    /// assert_eq!(
+    ///     value.select(".name"),
+    ///     vec![&DataValue::String("doson".to_string())]
+    /// );
+    /// ```
+    pub fn select(&self, path: &str) -> Vec<&DataValue> {
+        Selector::compile(path)
+            .map(|selector| selector.select(self))
+            .unwrap_or_default()
+    }
+
+    /// Parses a stream of back-to-back doson values from any `Read`,
+    /// without buffering the whole input and without requiring a separator
+    /// between values. Yields one `DataValue` as soon as it is fully read,
+    /// and surfaces a parse error instead of hanging on malformed input.
+    pub fn parse_stream<R: std::io::Read>(
+        reader: R,
+    ) -> impl Iterator<Item = anyhow::Result<DataValue>> {
+        stream::ValueStream::new(reader)
+    }
+
+}
+
+// 供 stream 模块复用：要求整段输入恰好是一个完整的值，不允许有多余的尾部内容
+pub(crate) fn parse_complete(data: &str) -> anyhow::Result<DataValue> {
+    match ValueParser::parse(data.trim()) {
+        Ok((rest, value)) if rest.trim().is_empty() => Ok(value),
+        Ok((rest, _)) => Err(anyhow::anyhow!("unexpected trailing input `{}`", rest)),
+        Err(e) => Err(anyhow::anyhow!("parse error: {:?}", e)),
+    }
+}
+
+// `from`/`from_with` 共用的 `b:<base64>:` 外壳解包逻辑
+fn unwrap_b64_shorthand(data: &str) -> String {
+    if data.len() >= 3 && &data[0..2] == "b:" && &data[data.len() - 1..] == ":" {
+        let temp = &data[2..data.len() - 1];
+        let temp = base64::decode(temp).unwrap_or(vec![]);
+        return String::from_utf8(temp).unwrap_or(String::new());
+    }
+
+    data.to_string()
+}
+
+// 供 `from_with` 复用：解析一个值并把剩余未消费的输入一并返回；
+// 解析失败时返回失败位置在 `data`（即预处理之后的缓冲区）里的字节偏移量，
+// 由调用方负责把它映射回原始输入的偏移量
+fn parse_with_rest(data: &str) -> Result<(DataValue, &str), usize> {
+    match ValueParser::parse(data) {
+        Ok((rest, value)) => Ok((value, rest)),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(data.len() - e.input.len())
+        }
+        Err(nom::Err::Incomplete(_)) => Err(data.len()),
+    }
 }
 
 struct ValueParser {}
@@ -410,8 +680,37 @@ impl ValueParser {
         ))
     }
 
-    fn parse_number(message: &str) -> IResult<&str, f64> {
-        double(message)
+    // 不带小数点/指数的整数字面量优先解析为 Int（溢出 i64 时退化为 UInt），
+    // 其余情况（带 `.` 或 `e`）仍然走 double 解析为 Number
+    fn parse_integer_literal(message: &str) -> IResult<&str, &str> {
+        let (rest, matched) = nom::combinator::recognize(preceded(
+            nom::combinator::opt(tag("-")),
+            nom::character::complete::digit1,
+        ))(message)?;
+
+        if matches!(rest.chars().next(), Some('.') | Some('e') | Some('E')) {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                message,
+                nom::error::ErrorKind::Digit,
+            )));
+        }
+
+        Ok((rest, matched))
+    }
+
+    fn parse_number(message: &str) -> IResult<&str, DataValue> {
+        alt((
+            map(ValueParser::parse_integer_literal, |s: &str| {
+                if let Ok(n) = s.parse::<i64>() {
+                    DataValue::Int(n)
+                } else if let Ok(n) = s.parse::<u64>() {
+                    DataValue::UInt(n)
+                } else {
+                    DataValue::Number(s.parse::<f64>().unwrap_or(0_f64))
+                }
+            }),
+            map(double, DataValue::Number),
+        ))(message)
     }
 
     fn parse_boolean(message: &str) -> IResult<&str, bool> {
@@ -485,7 +784,7 @@ impl ValueParser {
             delimited(
                 multispace0,
                 alt((
-                    map(ValueParser::parse_number, DataValue::Number),
+                    ValueParser::parse_number,
                     map(ValueParser::parse_boolean, DataValue::Boolean),
                     map(ValueParser::parse_string, |s| {
                         DataValue::String(String::from(s))
@@ -514,12 +813,12 @@ mod test {
             Ok((
                 "",
                 DataValue::List(vec![
-                    DataValue::Number(1_f64),
-                    DataValue::Number(2_f64),
-                    DataValue::Number(3_f64),
-                    DataValue::Number(4_f64),
-                    DataValue::Number(5_f64),
-                    DataValue::Number(6_f64),
+                    DataValue::Int(1),
+                    DataValue::Int(2),
+                    DataValue::Int(3),
+                    DataValue::Int(4),
+                    DataValue::Int(5),
+                    DataValue::Int(6),
                 ])
             ))
         );
@@ -534,7 +833,7 @@ mod test {
                 "",
                 DataValue::Tuple((
                     Box::new(DataValue::Boolean(true)),
-                    Box::new(DataValue::Number(1_f64))
+                    Box::new(DataValue::Int(1))
                 ))
             ))
         );
@@ -549,6 +848,242 @@ mod test {
         )
     }
 
+    #[test]
+    fn dict_equality_ignores_insertion_order() {
+        let a = DataValue::Dict(std::collections::HashMap::from([
+            ("a".to_string(), DataValue::Number(1.0)),
+            ("b".to_string(), DataValue::Number(2.0)),
+        ]));
+        let b = DataValue::Dict(std::collections::HashMap::from([
+            ("b".to_string(), DataValue::Number(2.0)),
+            ("a".to_string(), DataValue::Number(1.0)),
+        ]));
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn dict_inequality_on_value_mismatch() {
+        let a = DataValue::Dict(std::collections::HashMap::from([
+            ("a".to_string(), DataValue::Number(1.0)),
+        ]));
+        let b = DataValue::Dict(std::collections::HashMap::from([
+            ("a".to_string(), DataValue::Number(2.0)),
+        ]));
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn ordering_falls_back_to_datatype_rank() {
+        assert!(DataValue::Boolean(true) < DataValue::Number(0.0));
+        assert!(DataValue::Number(100.0) < DataValue::String("a".to_string()));
+    }
+
+    #[test]
+    fn schema_validates_matching_value() {
+        let schema = crate::Schema::from(
+            "{\"type\": \"dict\", \"fields\": {\
+                \"name\": {\"type\": \"string\"}, \
+                \"age\": {\"type\": \"optional\", \"inner\": {\"type\": \"number\", \"min\": 0}}\
+            }}",
+        );
+        let value = DataValue::from("{\"name\": \"ok\", \"age\": 5}");
+        assert_eq!(schema.validate(&value), Ok(()));
+    }
+
+    #[test]
+    fn schema_reports_every_mismatch() {
+        let schema = crate::Schema::from(
+            "{\"type\": \"dict\", \"fields\": {\
+                \"name\": {\"type\": \"string\"}, \
+                \"age\": {\"type\": \"number\", \"min\": 0, \"max\": 120}\
+            }}",
+        );
+        let value = DataValue::from("{\"age\": -5}");
+        let errors = schema.validate(&value).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.path == "$.name"));
+        assert!(errors.iter().any(|e| e.path == "$.age"));
+    }
+
+    #[test]
+    fn schema_is_an_ordinary_doson_document() {
+        let src = "{\"type\": \"dict\", \"fields\": {\"name\": {\"type\": \"string\"}}}";
+
+        // the schema source is valid doson on its own, not a separate grammar
+        let value = DataValue::from(src);
+        assert_ne!(value, DataValue::None);
+        assert_eq!(crate::Schema::from_value(&value), Some(crate::Schema::from(src)));
+
+        // so it survives the same round trips any other document does
+        let roundtripped = DataValue::from_bytes(&value.to_bytes()).unwrap();
+        assert_eq!(crate::Schema::from_value(&roundtripped), crate::Schema::from_value(&value));
+    }
+
+    #[test]
+    fn select_field_and_index() {
+        let value = DataValue::from("{\"items\": [1, 2, 3]}");
+        assert_eq!(value.select(".items[1]"), vec![&DataValue::Int(2)]);
+    }
+
+    #[test]
+    fn select_predicate_filters_dicts() {
+        let value = DataValue::from("[{\"age\": 30}, {\"age\": 12}]");
+        assert_eq!(
+            value.select("[*][?age>18]"),
+            vec![&DataValue::from("{\"age\": 30}")]
+        );
+    }
+
+    #[test]
+    fn select_recursive_descent() {
+        let value = DataValue::from("{\"a\": {\"name\": \"x\"}, \"b\": {\"name\": \"y\"}}");
+        let mut names: Vec<String> = value
+            .select("..name")
+            .into_iter()
+            .filter_map(|v| v.as_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["x".to_string(), "y".to_string()]);
+    }
+
+    #[test]
+    fn select_malformed_returns_empty() {
+        let value = DataValue::from("{\"a\": 1}");
+        assert_eq!(value.select("[?bad"), Vec::<&DataValue>::new());
+    }
+
+    #[test]
+    fn parse_stream_yields_concatenated_values() {
+        let input = "[1,2,3]{\"a\":true} \"hello\" 42";
+        let values: Vec<DataValue> = DataValue::parse_stream(input.as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                DataValue::List(vec![DataValue::Int(1), DataValue::Int(2), DataValue::Int(3)]),
+                DataValue::Dict(std::collections::HashMap::from([
+                    ("a".to_string(), DataValue::Boolean(true)),
+                ])),
+                DataValue::String("hello".to_string()),
+                DataValue::Int(42),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_stream_reports_truncated_input() {
+        let input = "[1,2,3";
+        let results: Vec<_> = DataValue::parse_stream(input.as_bytes()).collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn to_bytes_roundtrip() {
+        let value = DataValue::Tuple((
+            Box::new(DataValue::String("doson".to_string())),
+            Box::new(DataValue::List(vec![
+                DataValue::Number(1.0),
+                DataValue::Boolean(true),
+                DataValue::None,
+            ])),
+        ));
+
+        assert_eq!(
+            DataValue::from_bytes(&value.to_bytes()).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_input() {
+        let bytes = DataValue::String("hello".to_string()).to_bytes();
+        assert!(DataValue::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn from_bytes_rejects_unknown_tag() {
+        assert!(DataValue::from_bytes(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn from_bytes_rejects_forged_huge_count_without_panicking() {
+        // tag List, followed by a LEB128-encoded 2^56 element count
+        let list = [0x04, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01];
+        assert!(DataValue::from_bytes(&list).is_err());
+
+        // same forged-count issue for Dict
+        let dict = [0x05, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01];
+        assert!(DataValue::from_bytes(&dict).is_err());
+    }
+
+    #[test]
+    fn from_with_allows_trailing_commas_and_comments() {
+        let options = crate::ParserOptions::new().trailing_commas(true).comments(true);
+        let value = DataValue::from_with(
+            "[1, 2, /* keep going */ 3, // trailing comma below\n]",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            DataValue::List(vec![DataValue::Int(1), DataValue::Int(2), DataValue::Int(3)])
+        );
+    }
+
+    #[test]
+    fn from_with_allows_unquoted_keys() {
+        let options = crate::ParserOptions::new().unquoted_keys(true);
+        let value = DataValue::from_with("{foo: 1, bar: 2}", &options).unwrap();
+        assert_eq!(value, DataValue::from("{\"foo\": 1, \"bar\": 2}"));
+    }
+
+    #[test]
+    fn from_with_default_matches_from() {
+        let value = DataValue::from_with("[1,2,3]", &crate::ParserOptions::default()).unwrap();
+        assert_eq!(value, DataValue::from("[1,2,3]"));
+    }
+
+    #[test]
+    fn from_with_strict_reports_trailing_input() {
+        let options = crate::ParserOptions::new().strict(true);
+        let input = "[1,2] garbage";
+        let err = DataValue::from_with(input, &options).unwrap_err();
+        // "garbage" starts right where it does in the original input, since
+        // no lenient option that rewrites the buffer is enabled here
+        assert!(err.to_string().contains(&format!("byte offset {}", input.find("garbage").unwrap())));
+    }
+
+    #[test]
+    fn from_with_strict_offset_accounts_for_stripped_comments() {
+        let options = crate::ParserOptions::new().comments(true).strict(true);
+        let input = "// leading comment\n[1,2] garbage";
+        let err = DataValue::from_with(input, &options).unwrap_err();
+        // The offset must point at `garbage` in the *original* input, not in
+        // the comment-stripped buffer the parser actually sees
+        assert!(err.to_string().contains(&format!("byte offset {}", input.find("garbage").unwrap())));
+    }
+
+    #[test]
+    fn from_with_non_strict_swallows_errors() {
+        let value = DataValue::from_with("not valid doson", &crate::ParserOptions::default()).unwrap();
+        assert_eq!(value, DataValue::None);
+    }
+
+    #[test]
+    fn from_with_default_decodes_b64_shorthand_like_from() {
+        let encoded = format!("b:{}:", base64::encode("[1,2,3]"));
+        assert_eq!(
+            DataValue::from_with(&encoded, &crate::ParserOptions::default()).unwrap(),
+            DataValue::from(&encoded)
+        );
+    }
+
     #[test]
     fn to_json() {
         let value = DataValue::List(vec![
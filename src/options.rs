@@ -0,0 +1,217 @@
+//! Configurable parsing: trailing commas, `//`/`/* */` comments, and
+//! unquoted identifier keys in `Dict` literals, plus an optional strict mode.
+//! These are implemented as a lenient-to-strict preprocessing pass that
+//! rewrites the relaxed syntax into the grammar [`crate::ValueParser`]
+//! already understands, so the core parser stays unchanged.
+
+/// Builder for the parsing behavior used by [`crate::DataValue::from_with`].
+///
+/// The default matches [`crate::DataValue::from`]: no trailing commas, no
+/// comments, no unquoted keys, and non-strict (parse errors yield
+/// `DataValue::None` instead of an error).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParserOptions {
+    pub(crate) trailing_commas: bool,
+    pub(crate) comments: bool,
+    pub(crate) unquoted_keys: bool,
+    pub(crate) strict: bool,
+}
+
+impl ParserOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow a trailing `,` before `]`, `}`, or `)`.
+    pub fn trailing_commas(mut self, enabled: bool) -> Self {
+        self.trailing_commas = enabled;
+        self
+    }
+
+    /// Allow `//` line comments and `/* */` block comments, treated as whitespace.
+    pub fn comments(mut self, enabled: bool) -> Self {
+        self.comments = enabled;
+        self
+    }
+
+    /// Allow unquoted identifier keys in `Dict` literals, e.g. `{foo: 1}`.
+    pub fn unquoted_keys(mut self, enabled: bool) -> Self {
+        self.unquoted_keys = enabled;
+        self
+    }
+
+    /// Require the entire input to be consumed; a parse failure or leftover
+    /// trailing input becomes a descriptive error instead of `DataValue::None`.
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+}
+
+/// The result of [`preprocess`]: the rewritten text, plus enough information
+/// to translate a byte offset into it back into an offset into the original
+/// input (used for [`crate::DataValue::from_with`]'s strict-mode errors).
+pub(crate) struct Preprocessed {
+    pub(crate) text: String,
+    // source_offsets[i] 是 text 中第 i 个字节在原始输入里对应的字节下标；
+    // 长度恰好比 text 多一项，最后一项是原始输入的总长度，用于映射"已读到末尾"的情况
+    source_offsets: Vec<usize>,
+}
+
+impl Preprocessed {
+    /// Maps a byte offset into [`Preprocessed::text`] back to the
+    /// corresponding byte offset in the original, unprocessed input.
+    pub(crate) fn original_offset(&self, processed_offset: usize) -> usize {
+        self.source_offsets
+            .get(processed_offset)
+            .copied()
+            .unwrap_or_else(|| *self.source_offsets.last().unwrap_or(&0))
+    }
+}
+
+/// Rewrites relaxed syntax enabled by `options` into the strict grammar the
+/// core parser accepts. Comments and trailing commas are dropped; unquoted
+/// dict keys are wrapped in quotes. String literals are left untouched.
+pub(crate) fn preprocess(input: &str, options: &ParserOptions) -> Preprocessed {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut source_offsets = Vec::with_capacity(bytes.len() + 1);
+    let mut i = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    // 上一个有效字符（跳过空白/注释后），用于判断当前位置是否处于 dict key 的位置
+    let mut prev_sig: u8 = 0;
+
+    macro_rules! push {
+        ($byte:expr, $source:expr) => {{
+            out.push($byte);
+            source_offsets.push($source);
+        }};
+    }
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        if in_string {
+            push!(b, i);
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+                prev_sig = b'"';
+            }
+            i += 1;
+            continue;
+        }
+
+        if options.comments && b == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            i += 2;
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        if options.comments && b == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            i += 2;
+            while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(bytes.len());
+            continue;
+        }
+
+        if b.is_ascii_whitespace() {
+            push!(b, i);
+            i += 1;
+            continue;
+        }
+
+        if b == b'"' {
+            in_string = true;
+            push!(b, i);
+            prev_sig = b;
+            i += 1;
+            continue;
+        }
+
+        if options.trailing_commas && b == b',' {
+            if let Some(next) = next_significant(bytes, i + 1, options) {
+                if matches!(bytes[next], b']' | b'}' | b')') {
+                    i = next;
+                    continue;
+                }
+            }
+        }
+
+        if options.unquoted_keys
+            && (b.is_ascii_alphabetic() || b == b'_')
+            && matches!(prev_sig, b'{' | b',')
+        {
+            let start = i;
+            let mut end = i;
+            while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+                end += 1;
+            }
+
+            if let Some(colon) = next_significant(bytes, end, options) {
+                if bytes[colon] == b':' {
+                    push!(b'"', start);
+                    for (offset, byte) in bytes[start..end].iter().enumerate() {
+                        push!(*byte, start + offset);
+                    }
+                    push!(b'"', end);
+                    prev_sig = b'"';
+                    i = end;
+                    continue;
+                }
+            }
+        }
+
+        push!(b, i);
+        prev_sig = b;
+        i += 1;
+    }
+
+    source_offsets.push(input.len());
+
+    Preprocessed {
+        text: String::from_utf8(out).unwrap_or_default(),
+        source_offsets,
+    }
+}
+
+// 跳过空白和（在开启时的）注释，返回下一个有效字符的下标
+fn next_significant(bytes: &[u8], mut i: usize, options: &ParserOptions) -> Option<usize> {
+    loop {
+        if i >= bytes.len() {
+            return None;
+        }
+
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if options.comments && bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            i += 2;
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        if options.comments && bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            i += 2;
+            while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(bytes.len());
+            continue;
+        }
+
+        return Some(i);
+    }
+}
@@ -0,0 +1,281 @@
+//! Schema definitions and validation for `DataValue` trees.
+//!
+//! A [`Schema`] mirrors the `DataValue` value space and can be validated
+//! against a value with [`Schema::validate`], which accumulates every
+//! mismatch instead of stopping at the first one. A schema definition is
+//! itself written as an ordinary doson document, a `Dict` tagged with a
+//! `"type"` field, so it can be parsed, stored and transmitted with the same
+//! `DataValue`/`ValueParser` machinery as any other document (`to_bytes`,
+//! `to_json`, `select`, ...), e.g.:
+//!
+//! ```text
+//! {
+//!     "type": "dict",
+//!     "fields": {
+//!         "name": {"type": "string"},
+//!         "age": {"type": "optional", "inner": {"type": "number", "min": 0}},
+//!         "tags": {"type": "list", "item": {"type": "string"}}
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::DataValue;
+
+/// The expected shape of a `DataValue`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    None,
+    Boolean,
+    Number { min: Option<f64>, max: Option<f64> },
+    String { pattern: Option<String> },
+    List(Box<Schema>),
+    Tuple(Box<Schema>, Box<Schema>),
+    Dict(HashMap<String, Schema>),
+    Binary { max_size: Option<usize> },
+    OneOf(Vec<Schema>),
+    /// Marks a `Dict` field as allowed to be absent (or explicitly `None`).
+    Optional(Box<Schema>),
+}
+
+/// A single schema mismatch, pointing at the offending node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl Schema {
+    /// Validates `value` against this schema, collecting every mismatch
+    /// rather than stopping at the first one.
+    pub fn validate(&self, value: &DataValue) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        validate_at(self, value, "$", &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parses a schema definition written as a doson document (see the
+    /// module docs). Goes through the ordinary `DataValue` parser first, so
+    /// the same text can also be loaded with `DataValue::from` and carried
+    /// around with `to_bytes`/`to_json`/`select` like any other document.
+    /// Returns `Schema::None` on malformed input, mirroring `DataValue::from`.
+    pub fn from(data: &str) -> Self {
+        Self::from_value(&DataValue::from(data)).unwrap_or(Schema::None)
+    }
+
+    /// Converts an already-parsed `DataValue` document into a `Schema`,
+    /// returning `None` if it isn't a validly tagged schema tree.
+    pub fn from_value(value: &DataValue) -> Option<Schema> {
+        let DataValue::Dict(dict) = value else {
+            return None;
+        };
+
+        let ty = match dict.get("type")? {
+            DataValue::String(s) => s.as_str(),
+            _ => return None,
+        };
+
+        match ty {
+            "none" => Some(Schema::None),
+            "boolean" => Some(Schema::Boolean),
+            "number" => Some(Schema::Number {
+                min: dict.get("min").and_then(as_f64),
+                max: dict.get("max").and_then(as_f64),
+            }),
+            "string" => Some(Schema::String {
+                pattern: match dict.get("pattern") {
+                    Some(DataValue::String(s)) => Some(s.clone()),
+                    _ => None,
+                },
+            }),
+            "binary" => Some(Schema::Binary {
+                max_size: dict.get("max_size").and_then(as_f64).map(|n| n as usize),
+            }),
+            "list" => Some(Schema::List(Box::new(Schema::from_value(dict.get("item")?)?))),
+            "tuple" => Some(Schema::Tuple(
+                Box::new(Schema::from_value(dict.get("first")?)?),
+                Box::new(Schema::from_value(dict.get("second")?)?),
+            )),
+            "dict" => {
+                let DataValue::Dict(fields) = dict.get("fields")? else {
+                    return None;
+                };
+
+                let mut result = HashMap::with_capacity(fields.len());
+                for (key, field_schema) in fields {
+                    result.insert(key.clone(), Schema::from_value(field_schema)?);
+                }
+
+                Some(Schema::Dict(result))
+            }
+            "oneof" => {
+                let DataValue::List(options) = dict.get("options")? else {
+                    return None;
+                };
+
+                let mut result = Vec::with_capacity(options.len());
+                for option in options {
+                    result.push(Schema::from_value(option)?);
+                }
+
+                Some(Schema::OneOf(result))
+            }
+            "optional" => Some(Schema::Optional(Box::new(Schema::from_value(dict.get("inner")?)?))),
+            _ => None,
+        }
+    }
+}
+
+fn as_f64(value: &DataValue) -> Option<f64> {
+    match value {
+        DataValue::Number(n) => Some(*n),
+        DataValue::Int(n) => Some(*n as f64),
+        DataValue::UInt(n) => Some(*n as f64),
+        _ => None,
+    }
+}
+
+fn error(errors: &mut Vec<ValidationError>, path: &str, message: impl Into<String>) {
+    errors.push(ValidationError {
+        path: path.to_string(),
+        message: message.into(),
+    });
+}
+
+fn validate_at(schema: &Schema, value: &DataValue, path: &str, errors: &mut Vec<ValidationError>) {
+    match schema {
+        Schema::None => {
+            if !matches!(value, DataValue::None) {
+                error(errors, path, format!("expected None, got {}", value.datatype()));
+            }
+        }
+        Schema::Boolean => {
+            if !matches!(value, DataValue::Boolean(_)) {
+                error(errors, path, format!("expected Boolean, got {}", value.datatype()));
+            }
+        }
+        Schema::Number { min, max } => {
+            let n = match value {
+                DataValue::Number(n) => Some(*n),
+                DataValue::Int(n) => Some(*n as f64),
+                DataValue::UInt(n) => Some(*n as f64),
+                _ => None,
+            };
+
+            match n {
+                Some(n) => {
+                    if let Some(min) = min {
+                        if n < *min {
+                            error(errors, path, format!("{} is below minimum {}", n, min));
+                        }
+                    }
+                    if let Some(max) = max {
+                        if n > *max {
+                            error(errors, path, format!("{} is above maximum {}", n, max));
+                        }
+                    }
+                }
+                None => error(errors, path, format!("expected Number, got {}", value.datatype())),
+            }
+        }
+        Schema::String { pattern } => match value {
+            DataValue::String(s) => {
+                if let Some(pattern) = pattern {
+                    if !glob_match(pattern, s) {
+                        error(errors, path, format!("\"{}\" does not match pattern \"{}\"", s, pattern));
+                    }
+                }
+            }
+            _ => error(errors, path, format!("expected String, got {}", value.datatype())),
+        },
+        Schema::Binary { max_size } => match value {
+            DataValue::Binary(bin) => {
+                if let Some(max_size) = max_size {
+                    if bin.size() > *max_size {
+                        error(errors, path, format!("binary of {} bytes exceeds max_size {}", bin.size(), max_size));
+                    }
+                }
+            }
+            _ => error(errors, path, format!("expected Binary, got {}", value.datatype())),
+        },
+        Schema::List(inner) => match value {
+            DataValue::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(inner, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+            _ => error(errors, path, format!("expected List, got {}", value.datatype())),
+        },
+        Schema::Tuple(first, second) => match value {
+            DataValue::Tuple(tuple) => {
+                validate_at(first, &tuple.0, &format!("{}.0", path), errors);
+                validate_at(second, &tuple.1, &format!("{}.1", path), errors);
+            }
+            _ => error(errors, path, format!("expected Tuple, got {}", value.datatype())),
+        },
+        Schema::Dict(fields) => match value {
+            DataValue::Dict(dict) => {
+                for (key, field_schema) in fields {
+                    let field_path = format!("{}.{}", path, key);
+                    match dict.get(key) {
+                        Some(v) => validate_at(field_schema, v, &field_path, errors),
+                        None => {
+                            if !matches!(field_schema, Schema::Optional(_)) {
+                                error(errors, &field_path, "missing required key");
+                            }
+                        }
+                    }
+                }
+
+                for key in dict.keys() {
+                    if !fields.contains_key(key) {
+                        error(errors, &format!("{}.{}", path, key), "unexpected key");
+                    }
+                }
+            }
+            _ => error(errors, path, format!("expected Dict, got {}", value.datatype())),
+        },
+        Schema::OneOf(options) => {
+            let matched = options.iter().any(|option| option.validate(value).is_ok());
+            if !matched {
+                error(errors, path, format!("value did not match any of {} schemas", options.len()));
+            }
+        }
+        Schema::Optional(inner) => {
+            if !matches!(value, DataValue::None) {
+                validate_at(inner, value, path, errors);
+            }
+        }
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_inner(&pattern, &text)
+}
+
+fn glob_match_inner(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => {
+            glob_match_inner(&pattern[1..], text)
+                || (!text.is_empty() && glob_match_inner(pattern, &text[1..]))
+        }
+        Some('?') => !text.is_empty() && glob_match_inner(&pattern[1..], &text[1..]),
+        Some(c) => text.first() == Some(c) && glob_match_inner(&pattern[1..], &text[1..]),
+    }
+}
@@ -0,0 +1,278 @@
+//! JSONPath-like selector queries over a `DataValue` tree.
+//!
+//! A selector string is compiled once into a sequence of [`Step`]s and then
+//! applied to a `DataValue`, returning every matching node. Supported steps:
+//! `.key` (descend into a `Dict` field, or `.0`/`.1` for a `Tuple`), `[n]`
+//! (index a `List` or `Tuple`), `[*]` (every child of a `List`/`Dict`/`Tuple`),
+//! `..` (recursive descent, matching the remainder of the selector at any
+//! depth), and `[?key==value]` style predicates that keep `Dict` nodes whose
+//! field satisfies the comparison.
+
+use std::collections::HashMap;
+
+use nom::{
+    IResult,
+    branch::alt,
+    bytes::complete::{tag, take_till, take_while1},
+    character::complete::digit1,
+    combinator::map,
+    multi::many0,
+    number::complete::double,
+    sequence::delimited,
+};
+
+use crate::DataValue;
+
+#[derive(Debug, Clone, PartialEq)]
+enum PredOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Predicate {
+    key: String,
+    op: PredOp,
+    value: DataValue,
+}
+
+impl Predicate {
+    fn matches(&self, dict: &HashMap<String, DataValue>) -> bool {
+        let Some(field) = dict.get(&self.key) else {
+            return false;
+        };
+
+        match self.op {
+            PredOp::Eq => field == &self.value,
+            PredOp::Ne => field != &self.value,
+            PredOp::Gt => field > &self.value,
+            PredOp::Ge => field >= &self.value,
+            PredOp::Lt => field < &self.value,
+            PredOp::Le => field <= &self.value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Field(String),
+    Index(usize),
+    Wildcard,
+    Recursive,
+    Predicate(Predicate),
+}
+
+/// A compiled selector, ready to be run against any number of `DataValue` trees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    steps: Vec<Step>,
+}
+
+impl Selector {
+    /// Compiles a selector string, returning a parse error for malformed input.
+    pub fn compile(path: &str) -> anyhow::Result<Selector> {
+        let (rest, step_groups) = many0(parse_step)(path)
+            .map_err(|e| anyhow::anyhow!("invalid selector `{}`: {:?}", path, e))?;
+        let steps: Vec<Step> = step_groups.into_iter().flatten().collect();
+
+        if !rest.is_empty() {
+            return Err(anyhow::anyhow!(
+                "invalid selector `{}`: unexpected trailing input `{}`",
+                path,
+                rest
+            ));
+        }
+
+        Ok(Selector { steps })
+    }
+
+    /// Returns every node in `value` matched by this selector.
+    pub fn select<'a>(&self, value: &'a DataValue) -> Vec<&'a DataValue> {
+        apply_steps(value, &self.steps)
+    }
+}
+
+fn apply_steps<'a>(root: &'a DataValue, steps: &[Step]) -> Vec<&'a DataValue> {
+    if let Some(pos) = steps.iter().position(|step| *step == Step::Recursive) {
+        let remaining = &steps[pos + 1..];
+        let mut results = Vec::new();
+        for value in apply_steps(root, &steps[..pos]) {
+            collect_recursive(value, remaining, &mut results);
+        }
+        return results;
+    }
+
+    let mut current = vec![root];
+    for step in steps {
+        let mut next = Vec::new();
+        for value in current {
+            apply_step(value, step, &mut next);
+        }
+        current = next;
+    }
+    current
+}
+
+fn collect_recursive<'a>(value: &'a DataValue, remaining: &[Step], results: &mut Vec<&'a DataValue>) {
+    results.extend(apply_steps(value, remaining));
+
+    match value {
+        DataValue::List(list) => {
+            for item in list {
+                collect_recursive(item, remaining, results);
+            }
+        }
+        DataValue::Dict(dict) => {
+            for item in dict.values() {
+                collect_recursive(item, remaining, results);
+            }
+        }
+        DataValue::Tuple(tuple) => {
+            collect_recursive(&tuple.0, remaining, results);
+            collect_recursive(&tuple.1, remaining, results);
+        }
+        _ => {}
+    }
+}
+
+fn apply_step<'a>(value: &'a DataValue, step: &Step, out: &mut Vec<&'a DataValue>) {
+    match step {
+        Step::Field(key) => match value {
+            DataValue::Dict(dict) => {
+                if let Some(v) = dict.get(key) {
+                    out.push(v);
+                }
+            }
+            DataValue::Tuple(tuple) => match key.as_str() {
+                "0" => out.push(&tuple.0),
+                "1" => out.push(&tuple.1),
+                _ => {}
+            },
+            _ => {}
+        },
+        Step::Index(index) => match value {
+            DataValue::List(list) => {
+                if let Some(v) = list.get(*index) {
+                    out.push(v);
+                }
+            }
+            DataValue::Tuple(tuple) => match index {
+                0 => out.push(&tuple.0),
+                1 => out.push(&tuple.1),
+                _ => {}
+            },
+            _ => {}
+        },
+        Step::Wildcard => match value {
+            DataValue::List(list) => out.extend(list.iter()),
+            DataValue::Dict(dict) => out.extend(dict.values()),
+            DataValue::Tuple(tuple) => {
+                out.push(&tuple.0);
+                out.push(&tuple.1);
+            }
+            _ => {}
+        },
+        Step::Predicate(predicate) => {
+            if let DataValue::Dict(dict) = value {
+                if predicate.matches(dict) {
+                    out.push(value);
+                }
+            }
+        }
+        Step::Recursive => unreachable!("recursive steps are consumed by apply_steps"),
+    }
+}
+
+fn ident(i: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_alphanumeric() || c == '_')(i)
+}
+
+fn parse_field(i: &str) -> IResult<&str, Step> {
+    map(nom::sequence::preceded(tag("."), ident), |s: &str| {
+        Step::Field(s.to_string())
+    })(i)
+}
+
+// `..` descends recursively; JSONPath-style shorthand lets a field name
+// follow immediately with no separating dot (`..name`), so this yields up to
+// two steps in one go.
+fn parse_recursive(i: &str) -> IResult<&str, Vec<Step>> {
+    let (i, _) = tag("..")(i)?;
+    let (i, field) = nom::combinator::opt(ident)(i)?;
+
+    let mut steps = vec![Step::Recursive];
+    if let Some(name) = field {
+        steps.push(Step::Field(name.to_string()));
+    }
+
+    Ok((i, steps))
+}
+
+fn parse_step(i: &str) -> IResult<&str, Vec<Step>> {
+    alt((
+        parse_recursive,
+        map(parse_predicate, |s| vec![s]),
+        map(parse_wildcard, |s| vec![s]),
+        map(parse_index, |s| vec![s]),
+        map(parse_field, |s| vec![s]),
+    ))(i)
+}
+
+fn parse_index(i: &str) -> IResult<&str, Step> {
+    map(delimited(tag("["), digit1, tag("]")), |s: &str| {
+        Step::Index(s.parse().unwrap_or(0))
+    })(i)
+}
+
+fn parse_wildcard(i: &str) -> IResult<&str, Step> {
+    map(delimited(tag("["), tag("*"), tag("]")), |_| Step::Wildcard)(i)
+}
+
+fn parse_predicate(i: &str) -> IResult<&str, Step> {
+    map(
+        delimited(tag("[?"), parse_predicate_body, tag("]")),
+        Step::Predicate,
+    )(i)
+}
+
+fn parse_predicate_body(i: &str) -> IResult<&str, Predicate> {
+    let (i, key) = ident(i)?;
+    let (i, op) = alt((
+        tag("=="),
+        tag("!="),
+        tag(">="),
+        tag("<="),
+        tag(">"),
+        tag("<"),
+    ))(i)?;
+    let (i, value) = alt((
+        map(
+            delimited(tag("\""), take_till(|c| c == '"'), tag("\"")),
+            |s: &str| DataValue::String(s.to_string()),
+        ),
+        map(double, DataValue::Number),
+    ))(i)?;
+
+    let op = match op {
+        "==" => PredOp::Eq,
+        "!=" => PredOp::Ne,
+        ">=" => PredOp::Ge,
+        "<=" => PredOp::Le,
+        ">" => PredOp::Gt,
+        "<" => PredOp::Lt,
+        _ => unreachable!("alt only yields the tags listed above"),
+    };
+
+    Ok((
+        i,
+        Predicate {
+            key: key.to_string(),
+            op,
+            value,
+        },
+    ))
+}
@@ -0,0 +1,126 @@
+//! Incremental parsing of back-to-back doson values from a `Read`r, without
+//! buffering the whole input up front and without requiring a separator
+//! between values. A value boundary is found by tracking bracket/brace/paren
+//! and quote nesting: a bare token (number, boolean) ends at the next
+//! whitespace, a quoted string ends at its closing quote, and a composite
+//! value ends when its brackets return to depth zero.
+
+use std::io::{BufReader, Read};
+
+use crate::DataValue;
+
+/// Iterator returned by [`DataValue::parse_stream`].
+pub struct ValueStream<R: Read> {
+    bytes: std::io::Bytes<BufReader<R>>,
+    buf: Vec<u8>,
+    depth: i32,
+    in_string: bool,
+    escaped: bool,
+    done: bool,
+}
+
+impl<R: Read> ValueStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            bytes: BufReader::new(reader).bytes(),
+            buf: Vec::new(),
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            done: false,
+        }
+    }
+
+    // 吃掉一个字节，返回 Some(..) 表示刚好凑成一个完整的值
+    fn feed(&mut self, byte: u8) -> Option<anyhow::Result<DataValue>> {
+        if self.buf.is_empty() && byte.is_ascii_whitespace() {
+            return None;
+        }
+
+        if self.in_string {
+            self.buf.push(byte);
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+                if self.depth == 0 {
+                    return Some(self.flush());
+                }
+            }
+            return None;
+        }
+
+        if !self.buf.is_empty() && self.depth == 0 && byte.is_ascii_whitespace() {
+            return Some(self.flush());
+        }
+
+        self.buf.push(byte);
+        match byte {
+            b'"' => self.in_string = true,
+            b'[' | b'{' | b'(' => self.depth += 1,
+            b']' | b'}' | b')' => {
+                self.depth -= 1;
+                if self.depth == 0 {
+                    return Some(self.flush());
+                }
+                if self.depth < 0 {
+                    return Some(Err(anyhow::anyhow!("unbalanced closing bracket in stream")));
+                }
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn flush(&mut self) -> anyhow::Result<DataValue> {
+        let text = String::from_utf8(std::mem::take(&mut self.buf))
+            .map_err(|e| anyhow::anyhow!("invalid utf-8 in stream: {}", e))?;
+        self.depth = 0;
+        self.in_string = false;
+        self.escaped = false;
+        crate::parse_complete(&text)
+    }
+}
+
+impl<R: Read> Iterator for ValueStream<R> {
+    type Item = anyhow::Result<DataValue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            match self.bytes.next() {
+                Some(Ok(byte)) => {
+                    if let Some(result) = self.feed(byte) {
+                        if result.is_err() {
+                            self.done = true;
+                        }
+                        return Some(result);
+                    }
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+                None => {
+                    self.done = true;
+
+                    if self.buf.is_empty() {
+                        return None;
+                    }
+
+                    if self.in_string || self.depth != 0 {
+                        return Some(Err(anyhow::anyhow!("truncated input at end of stream")));
+                    }
+
+                    return Some(self.flush());
+                }
+            }
+        }
+    }
+}